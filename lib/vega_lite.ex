@@ -97,7 +97,7 @@ defmodule VegaLite do
 
   defstruct spec: %{"$schema" => @schema_url}
 
-  alias VegaLite.Utils
+  alias VegaLite.{LZString, Utils}
 
   @type t :: %__MODULE__{
           spec: spec()
@@ -161,6 +161,37 @@ defmodule VegaLite do
     |> from_spec()
   end
 
+  @compile {:no_warn_undefined, {Jason, :decode, 1}}
+
+  @doc """
+  Parses the Vega-Lite specification embedded in the given
+  [Vega Editor](https://vega.github.io/editor) URL.
+
+  This is the counterpart of `VegaLite.Export.to_editor_url/1`
+  and accepts the links generated by the editor when sharing
+  a graphic.
+
+  ## Examples
+
+      Vl.from_editor_url("https://vega.github.io/editor/#/url/vega-lite/N4IgJAzgxgFgp...")
+
+  """
+  @spec from_editor_url(String.t()) :: t()
+  def from_editor_url(url) do
+    Utils.assert_jason!("from_editor_url/1")
+
+    with %URI{fragment: "/url/vega-lite/" <> path} <- URI.parse(url),
+         [compressed | _] = String.split(path, "/"),
+         {:ok, json} <- LZString.decompress_from_encoded_uri_component(compressed),
+         {:ok, spec} when is_map(spec) <- Jason.decode(json) do
+      from_spec(spec)
+    else
+      _ ->
+        raise ArgumentError,
+              "expected a Vega Editor URL with an embedded Vega-Lite specification, got: #{inspect(url)}"
+    end
+  end
+
   @doc """
   Wraps the given Vega-Lite specification in the `VegaLite`
   struct for further processing.
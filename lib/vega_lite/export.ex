@@ -11,7 +11,7 @@ defmodule VegaLite.Export do
       npm install vega vega-lite canvas
   """
 
-  alias VegaLite.Utils
+  alias VegaLite.{LZString, Utils}
 
   @doc """
   Saves a `VegaLite` specification to file in one of
@@ -22,6 +22,9 @@ defmodule VegaLite.Export do
     * `:format` - the format to export the graphic as,
       must be either of: `:json`, `:html`, `:png`, `:svg`, `:pdf`.
       By default the format is inferred from the file extension.
+
+  All other options are passed to `to_png/2`, `to_svg/2` or `to_pdf/2`
+  respectively.
   """
   @spec save!(VegaLite.t(), binary(), keyword()) :: :ok
   def save!(vl, path, opts \\ []) do
@@ -39,13 +42,13 @@ defmodule VegaLite.Export do
           to_html(vl)
 
         :png ->
-          to_png(vl)
+          to_png(vl, opts)
 
         :svg ->
-          to_svg(vl)
+          to_svg(vl, opts)
 
         :pdf ->
-          to_pdf(vl)
+          to_pdf(vl, opts)
 
         _ ->
           raise ArgumentError,
@@ -105,15 +108,36 @@ defmodule VegaLite.Export do
     String.replace(json, ~s{"}, ~s{\\"})
   end
 
+  @editor_url "https://vega.github.io/editor/#/url/vega-lite/"
+
+  @doc """
+  Returns a [Vega Editor](https://vega.github.io/editor) URL
+  with the given graphic embedded.
+
+  The specification is compressed into the URL, the same way
+  the editor does when sharing a graphic, so opening the link
+  shows the graphic along with its source.
+  """
+  @spec to_editor_url(VegaLite.t()) :: String.t()
+  def to_editor_url(vl) do
+    json = to_json(vl)
+    @editor_url <> LZString.compress_to_encoded_uri_component(json)
+  end
+
   @doc """
   Renders the given graphic as a PNG image and returns
   its binary content.
 
   Relies on the `npm` packages mentioned above.
+
+  ## Options
+
+    * `:max_output_bytes` - raises an error if the output is larger
+      than the given number of bytes. By default there is no limit
   """
-  @spec to_png(VegaLite.t()) :: binary()
-  def to_png(vl) do
-    node_convert(vl, "png", "to_png/1")
+  @spec to_png(VegaLite.t(), keyword()) :: binary()
+  def to_png(vl, opts \\ []) do
+    node_convert(vl, "png", "to_png/2", opts)
   end
 
   @doc """
@@ -121,10 +145,59 @@ defmodule VegaLite.Export do
   its binary content.
 
   Relies on the `npm` packages mentioned above.
+
+  ## Options
+
+    * `:responsive` - when `true`, the fixed `width` and `height`
+      attributes are removed from the image, so that it scales
+      with its container, as given by the `viewBox`. Defaults to `false`
+
+    * `:max_output_bytes` - raises an error if the output is larger
+      than the given number of bytes. By default there is no limit
   """
-  @spec to_svg(VegaLite.t()) :: binary()
-  def to_svg(vl) do
-    node_convert(vl, "svg", "to_svg/1")
+  @spec to_svg(VegaLite.t(), keyword()) :: binary()
+  def to_svg(vl, opts \\ []) do
+    vl
+    |> node_convert("svg", "to_svg/2", opts)
+    |> maybe_make_svg_responsive(opts)
+  end
+
+  defp maybe_make_svg_responsive(svg, opts) do
+    if Keyword.get(opts, :responsive, false) do
+      make_svg_responsive(svg)
+    else
+      svg
+    end
+  end
+
+  # Rewrites the root element of the given SVG image, so that it
+  # scales with its container
+  defp make_svg_responsive(svg) do
+    Regex.replace(~r/<svg\b[^>]*>/, svg, &make_svg_root_responsive/1, global: false)
+  end
+
+  defp make_svg_root_responsive(root) do
+    width = svg_attribute(root, "width")
+    height = svg_attribute(root, "height")
+
+    root =
+      if svg_attribute(root, "viewBox") == nil and width != nil and height != nil do
+        view_box = ~s{viewBox="0 0 #{width} #{height}"}
+        String.replace(root, "<svg", "<svg " <> view_box, global: false)
+      else
+        root
+      end
+
+    root
+    |> String.replace(~r/\s(width|height|preserveAspectRatio)="[^"]*"/, "")
+    |> String.replace("<svg", ~s{<svg preserveAspectRatio="xMidYMid meet"}, global: false)
+  end
+
+  defp svg_attribute(root, name) do
+    case Regex.run(~r/\s#{name}="([^"]*)"/, root) do
+      [_, value] -> value
+      nil -> nil
+    end
   end
 
   @doc """
@@ -132,25 +205,168 @@ defmodule VegaLite.Export do
   binary content.
 
   Relies on the `npm` packages mentioned above.
+
+  ## Options
+
+    * `:max_output_bytes` - raises an error if the output is larger
+      than the given number of bytes. By default there is no limit
   """
-  @spec to_pdf(VegaLite.t()) :: binary()
-  def to_pdf(vl) do
-    node_convert(vl, "pdf", "to_pdf/1")
+  @spec to_pdf(VegaLite.t(), keyword()) :: binary()
+  def to_pdf(vl, opts \\ []) do
+    node_convert(vl, "pdf", "to_pdf/2", opts)
   end
 
-  defp node_convert(vl, format, fn_name) do
+  @doc """
+  Renders the given graphic and returns a document snippet
+  with the image embedded as a data URI.
+
+  This is useful for assembling reports out of multiple graphics.
+
+  Relies on the `npm` packages mentioned above.
+
+  ## Options
+
+    * `:format` - the image format, either `:png` or `:svg`.
+      Defaults to `:png`
+
+    * `:markup` - the snippet markup, either `:html` for a `<figure>`
+      element or `:markdown` for an image block. Defaults to `:html`
+
+    * `:alt` - the alternative text of the image. Defaults to `""`
+
+    * `:caption` - the caption of the figure, rendered as the image
+      title in Markdown. By default no caption is included
+
+    * `:responsive` - when `true`, an SVG image scales with its
+      container, see `to_svg/2`. Defaults to `false`
+
+    * `:max_output_bytes` - raises an error if the image is larger
+      than the given number of bytes. By default there is no limit
+  """
+  @spec to_figure(VegaLite.t(), keyword()) :: String.t()
+  def to_figure(vl, opts \\ []) do
+    markup = Keyword.get(opts, :markup, :html)
+
+    if markup not in [:html, :markdown] do
+      raise ArgumentError,
+            "unsupported figure markup, expected :html or :markdown, got: #{inspect(markup)}"
+    end
+
+    {mime_type, image} =
+      case Keyword.get(opts, :format, :png) do
+        :png ->
+          {"image/png", node_convert(vl, "png", "to_figure/2", opts)}
+
+        :svg ->
+          svg = vl |> node_convert("svg", "to_figure/2", opts) |> maybe_make_svg_responsive(opts)
+          {"image/svg+xml", svg}
+
+        format ->
+          raise ArgumentError,
+                "unsupported figure format, expected :png or :svg, got: #{inspect(format)}"
+      end
+
+    src = "data:#{mime_type};base64," <> Base.encode64(image)
+    alt = Keyword.get(opts, :alt, "")
+    caption = opts[:caption]
+
+    case markup do
+      :html -> html_figure(src, alt, caption)
+      :markdown -> markdown_image(src, alt, caption)
+    end
+  end
+
+  defp html_figure(src, alt, caption) do
+    [
+      "<figure>",
+      ~s{  <img src="#{src}" alt="#{escape_html(alt)}">},
+      caption && "  <figcaption>#{escape_html(caption)}</figcaption>",
+      "</figure>"
+    ]
+    |> Enum.reject(&is_nil/1)
+    |> Enum.join("\n")
+  end
+
+  defp markdown_image(src, alt, nil) do
+    "![#{escape_markdown(alt)}](#{src})"
+  end
+
+  defp markdown_image(src, alt, caption) do
+    ~s{![#{escape_markdown(alt)}](#{src} "#{escape_markdown_title(caption)}")}
+  end
+
+  defp escape_html(text) do
+    text
+    |> String.replace("&", "&amp;")
+    |> String.replace("<", "&lt;")
+    |> String.replace(">", "&gt;")
+    |> String.replace(~s{"}, "&quot;")
+  end
+
+  defp escape_markdown(text) do
+    String.replace(text, ~r/[\\\[\]]/, "\\\\\\0")
+  end
+
+  # The title cannot span multiple lines, so line breaks are
+  # replaced with spaces
+  defp escape_markdown_title(text) do
+    text
+    |> String.replace("\\", "\\\\")
+    |> String.replace(~s{"}, ~s{\\"})
+    |> String.replace(~r/\r\n|\r|\n/, " ")
+  end
+
+  defp node_convert(vl, format, fn_name, opts) do
+    limit = opts[:max_output_bytes]
+
+    unless limit == nil or (is_integer(limit) and limit >= 0) do
+      raise ArgumentError,
+            "invalid :max_output_bytes option, expected a non-negative integer, got: #{inspect(limit)}"
+    end
+
     json = to_json(vl)
+
+    script_name = "vl2#{format}"
+    script_path = find_npm_script!(script_name, fn_name)
+
     json_file = System.tmp_dir!() |> Path.join("vega-lite-#{Utils.process_timestamp()}.json")
-    File.write!(json_file, json)
 
-    script_path = find_npm_script!("vl2#{format}", fn_name)
-    {output, 0} = run_cmd(script_path, [json_file])
+    # Make sure the temporary file is removed even if the command fails
+    result =
+      try do
+        File.write!(json_file, json)
+        run_cmd(script_path, [json_file])
+      after
+        _ = File.rm(json_file)
+      end
 
-    _ = File.rm(json_file)
+    output = script_output!(result, script_name, fn_name)
+
+    if limit do
+      validate_output_size!(output, limit, fn_name)
+    end
 
     output
   end
 
+  defp script_output!({output, 0}, _script_name, _fn_name), do: output
+
+  defp script_output!({_output, status}, script_name, fn_name) do
+    raise RuntimeError,
+          "#{fn_name} failed, because #{script_name} exited with status #{status}." <>
+            " Any error output of the script was printed to the terminal (stderr)"
+  end
+
+  defp validate_output_size!(output, limit, fn_name) do
+    size = byte_size(output)
+
+    if size > limit do
+      raise RuntimeError,
+            "#{fn_name} output is too large, got #{size} bytes," <>
+              " but :max_output_bytes is set to #{limit}"
+    end
+  end
+
   defp find_npm_script!(script_name, fn_name) do
     npm_path = System.find_executable("npm")
 
@@ -0,0 +1,239 @@
+defmodule VegaLite.LZString do
+  @moduledoc false
+
+  # An implementation of the URI-safe variant of the lz-string
+  # compression (https://github.com/pieroxy/lz-string), which is
+  # used by the Vega Editor to embed specifications in URLs.
+  #
+  # The algorithm operates on UTF-16 code units, so that the output
+  # is compatible with the JavaScript implementation.
+
+  import Bitwise
+
+  @alphabet "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+-$"
+  @alphabet_index @alphabet |> :binary.bin_to_list() |> Enum.with_index() |> Map.new()
+
+  @doc """
+  Compresses the given string into a URI-safe string.
+  """
+  @spec compress_to_encoded_uri_component(String.t()) :: String.t()
+  def compress_to_encoded_uri_component(string) do
+    state = %{
+      dict: %{},
+      to_create: MapSet.new(),
+      w: "",
+      enlarge_in: 2,
+      dict_size: 3,
+      num_bits: 2,
+      bits: <<>>
+    }
+
+    state =
+      string
+      |> utf16_units()
+      |> Enum.reduce(state, &compress_unit/2)
+      |> compress_flush()
+
+    # Mark the end of the stream and pad to a full character
+    bits = write_bits(state.bits, 2, state.num_bits)
+    padding = 6 - rem(bit_size(bits), 6)
+    bits = <<bits::bitstring, 0::size(padding)>>
+
+    for <<value::6 <- bits>>, into: "", do: binary_part(@alphabet, value, 1)
+  end
+
+  defp utf16_units(string) do
+    utf16 = :unicode.characters_to_binary(string, :utf8, {:utf16, :big})
+    for <<unit::binary-size(2) <- utf16>>, do: unit
+  end
+
+  defp compress_unit(c, state) do
+    state =
+      if Map.has_key?(state.dict, c) do
+        state
+      else
+        %{
+          state
+          | dict: Map.put(state.dict, c, state.dict_size),
+            dict_size: state.dict_size + 1,
+            to_create: MapSet.put(state.to_create, c)
+        }
+      end
+
+    wc = state.w <> c
+
+    if Map.has_key?(state.dict, wc) do
+      %{state | w: wc}
+    else
+      state = write_w(state)
+
+      %{
+        state
+        | dict: Map.put(state.dict, wc, state.dict_size),
+          dict_size: state.dict_size + 1,
+          w: c
+      }
+    end
+  end
+
+  defp compress_flush(%{w: ""} = state), do: state
+  defp compress_flush(state), do: write_w(state)
+
+  defp write_w(state) do
+    state =
+      if MapSet.member?(state.to_create, state.w) do
+        <<code::16, _::binary>> = state.w
+
+        bits =
+          if code < 256 do
+            state.bits |> write_bits(0, state.num_bits) |> write_bits(code, 8)
+          else
+            state.bits |> write_bits(1, state.num_bits) |> write_bits(code, 16)
+          end
+
+        state = %{state | bits: bits, to_create: MapSet.delete(state.to_create, state.w)}
+        decrement_enlarge_in(state)
+      else
+        value = Map.fetch!(state.dict, state.w)
+        %{state | bits: write_bits(state.bits, value, state.num_bits)}
+      end
+
+    decrement_enlarge_in(state)
+  end
+
+  # Values are written starting from the least significant bit
+  defp write_bits(bits, value, num_bits) do
+    Enum.reduce(0..(num_bits - 1), bits, fn i, bits ->
+      <<bits::bitstring, (value >>> i &&& 1)::1>>
+    end)
+  end
+
+  defp decrement_enlarge_in(state) do
+    case state.enlarge_in - 1 do
+      0 -> %{state | enlarge_in: 1 <<< state.num_bits, num_bits: state.num_bits + 1}
+      enlarge_in -> %{state | enlarge_in: enlarge_in}
+    end
+  end
+
+  @doc """
+  Decompresses a string produced by `compress_to_encoded_uri_component/1`.
+
+  Returns `:error` if the string is not a valid compressed input.
+  """
+  @spec decompress_from_encoded_uri_component(String.t()) :: {:ok, String.t()} | :error
+  def decompress_from_encoded_uri_component(string) do
+    string = String.replace(string, " ", "+")
+
+    with {:ok, bits} <- decode_alphabet(string) do
+      decompress(bits)
+    end
+  end
+
+  defp decode_alphabet(string) do
+    string
+    |> :binary.bin_to_list()
+    |> Enum.reduce_while({:ok, <<>>}, fn char, {:ok, bits} ->
+      case Map.fetch(@alphabet_index, char) do
+        {:ok, value} -> {:cont, {:ok, <<bits::bitstring, value::6>>}}
+        :error -> {:halt, :error}
+      end
+    end)
+  end
+
+  defp decompress(bits) do
+    case read_bits(bits, 2) do
+      {:ok, 2, _bits} ->
+        {:ok, ""}
+
+      {:ok, type, bits} ->
+        with {:ok, c, bits} <- read_unit(bits, type) do
+          state = %{dict: %{3 => c}, dict_size: 4, enlarge_in: 4, num_bits: 3, w: c}
+          decompress_loop(bits, state, [c])
+        end
+
+      :error ->
+        :error
+    end
+  end
+
+  defp decompress_loop(bits, state, acc) do
+    case read_bits(bits, state.num_bits) do
+      {:ok, 2, _bits} ->
+        acc
+        |> Enum.reverse()
+        |> IO.iodata_to_binary()
+        |> :unicode.characters_to_binary({:utf16, :big}, :utf8)
+        |> case do
+          string when is_binary(string) -> {:ok, string}
+          _ -> :error
+        end
+
+      {:ok, type, bits} when type in [0, 1] ->
+        with {:ok, c, bits} <- read_unit(bits, type) do
+          state = %{
+            state
+            | dict: Map.put(state.dict, state.dict_size, c),
+              dict_size: state.dict_size + 1
+          }
+
+          state = decrement_enlarge_in(state)
+          decompress_entry(bits, state, state.dict_size - 1, acc)
+        end
+
+      {:ok, code, bits} ->
+        decompress_entry(bits, state, code, acc)
+
+      :error ->
+        :error
+    end
+  end
+
+  defp decompress_entry(bits, state, code, acc) do
+    entry =
+      case state.dict do
+        %{^code => entry} -> entry
+        _ when code == state.dict_size -> state.w <> binary_part(state.w, 0, 2)
+        _ -> nil
+      end
+
+    if entry do
+      state = %{
+        state
+        | dict: Map.put(state.dict, state.dict_size, state.w <> binary_part(entry, 0, 2)),
+          dict_size: state.dict_size + 1,
+          w: entry
+      }
+
+      state = decrement_enlarge_in(state)
+      decompress_loop(bits, state, [entry | acc])
+    else
+      :error
+    end
+  end
+
+  defp read_unit(bits, 0), do: read_unit_of_size(bits, 8)
+  defp read_unit(bits, 1), do: read_unit_of_size(bits, 16)
+  defp read_unit(_bits, _type), do: :error
+
+  defp read_unit_of_size(bits, size) do
+    with {:ok, code, bits} <- read_bits(bits, size) do
+      {:ok, <<code::16>>, bits}
+    end
+  end
+
+  # Values are read starting from the least significant bit
+  defp read_bits(bits, num_bits) do
+    case bits do
+      <<chunk::bitstring-size(num_bits), rest::bitstring>> ->
+        value =
+          for(<<bit::1 <- chunk>>, do: bit)
+          |> Enum.reverse()
+          |> Enum.reduce(0, fn bit, acc -> acc * 2 + bit end)
+
+        {:ok, value, rest}
+
+      _ ->
+        :error
+    end
+  end
+end
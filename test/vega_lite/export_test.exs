@@ -0,0 +1,188 @@
+defmodule VegaLite.ExportTest do
+  use ExUnit.Case
+
+  alias VegaLite, as: Vl
+  alias VegaLite.Export
+
+  describe "to_editor_url/1" do
+    test "embeds the compressed specification in the url" do
+      vl = Vl.new() |> Vl.mark(:point)
+
+      assert Export.to_editor_url(vl) ==
+               "https://vega.github.io/editor/#/url/vega-lite/" <>
+                 "N4IgJAzgxgFgpgWwIYgFwhgF0wBwqgegIDc4BzJAOjIEtMYBXAI0poHsDp5kTykBaADZ04JAKyUAVhDYA7EABoQyAE4BrNCBxsaszCAC+QA"
+    end
+  end
+
+  describe "to_png/2" do
+    setup :stub_npm
+
+    test "raises an error when the npm script fails", %{bin: bin} do
+      write_script!(Path.join(bin, "vl2png"), "exit 3")
+
+      assert_raise RuntimeError,
+                   "to_png/2 failed, because vl2png exited with status 3." <>
+                     " Any error output of the script was printed to the terminal (stderr)",
+                   fn ->
+                     Export.to_png(Vl.new() |> Vl.mark(:point))
+                   end
+    end
+
+    test "raises an error when the output exceeds :max_output_bytes", %{bin: bin} do
+      write_script!(Path.join(bin, "vl2png"), "printf 0123456789")
+      vl = Vl.new() |> Vl.mark(:point)
+
+      assert Export.to_png(vl, max_output_bytes: 10) == "0123456789"
+
+      assert_raise RuntimeError,
+                   "to_png/2 output is too large, got 10 bytes, but :max_output_bytes is set to 5",
+                   fn ->
+                     Export.to_png(vl, max_output_bytes: 5)
+                   end
+    end
+
+    test "raises an error when invalid :max_output_bytes is given" do
+      vl = Vl.new() |> Vl.mark(:point)
+
+      for limit <- ["1000", -1, 1.5] do
+        assert_raise ArgumentError,
+                     "invalid :max_output_bytes option, expected a non-negative integer, got: #{inspect(limit)}",
+                     fn ->
+                       Export.to_png(vl, max_output_bytes: limit)
+                     end
+      end
+    end
+  end
+
+  describe "to_svg/2" do
+    setup :stub_npm
+
+    @svg ~s{<svg class="marks" width="400" height="300" viewBox="0 0 400 300">} <>
+           ~s{<rect width="10" height="20" stroke-width="2"></rect></svg>}
+
+    test "returns the image unchanged by default", %{bin: bin} do
+      write_svg_script!(bin, @svg)
+
+      assert Export.to_svg(Vl.new() |> Vl.mark(:point)) == @svg
+    end
+
+    test "removes width and height from the root element when responsive", %{bin: bin} do
+      write_svg_script!(bin, @svg)
+
+      svg = Export.to_svg(Vl.new() |> Vl.mark(:point), responsive: true)
+      [root] = Regex.run(~r/<svg[^>]*>/, svg)
+
+      refute root =~ ~r/\s(width|height)=/
+    end
+
+    test "leaves attributes of child elements unchanged when responsive", %{bin: bin} do
+      write_svg_script!(bin, @svg)
+
+      svg = Export.to_svg(Vl.new() |> Vl.mark(:point), responsive: true)
+
+      assert svg =~ ~s{<rect width="10" height="20" stroke-width="2"></rect>}
+    end
+
+    test "keeps the existing view box when responsive", %{bin: bin} do
+      write_svg_script!(bin, @svg)
+
+      svg = Export.to_svg(Vl.new() |> Vl.mark(:point), responsive: true)
+
+      assert svg =~ ~s{viewBox="0 0 400 300"}
+    end
+
+    test "adds preserveAspectRatio exactly once when responsive", %{bin: bin} do
+      write_svg_script!(bin, ~s{<svg width="400" height="300" preserveAspectRatio="none"></svg>})
+
+      assert Export.to_svg(Vl.new() |> Vl.mark(:point), responsive: true) ==
+               ~s{<svg preserveAspectRatio="xMidYMid meet" viewBox="0 0 400 300"></svg>}
+    end
+
+    test "creates the view box from width and height when missing", %{bin: bin} do
+      write_svg_script!(bin, ~s{<svg class="marks" width="400" height="300"></svg>})
+
+      assert Export.to_svg(Vl.new() |> Vl.mark(:point), responsive: true) ==
+               ~s{<svg preserveAspectRatio="xMidYMid meet" viewBox="0 0 400 300" class="marks"></svg>}
+    end
+  end
+
+  describe "to_figure/2" do
+    setup :stub_npm
+
+    test "returns an HTML figure with the image as data URI", %{bin: bin} do
+      write_script!(Path.join(bin, "vl2png"), "printf PNG")
+      vl = Vl.new() |> Vl.mark(:point)
+
+      expected = """
+      <figure>
+        <img src="data:image/png;base64,UE5H" alt="Points &amp; lines">
+        <figcaption>Figure &lt;1&gt;</figcaption>
+      </figure>
+      """
+
+      assert Export.to_figure(vl, alt: "Points & lines", caption: "Figure <1>") ==
+               String.trim_trailing(expected)
+    end
+
+    test "returns a Markdown image block", %{bin: bin} do
+      write_script!(Path.join(bin, "vl2svg"), "printf '<svg></svg>'")
+      vl = Vl.new() |> Vl.mark(:point)
+
+      assert Export.to_figure(vl, format: :svg, markup: :markdown, alt: "Chart [1]") ==
+               ~S{![Chart \[1\]](data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=)}
+
+      assert Export.to_figure(vl, format: :svg, markup: :markdown, caption: ~s{A "bar" chart}) ==
+               ~S{![](data:image/svg+xml;base64,PHN2Zz48L3N2Zz4= "A \"bar\" chart")}
+
+      assert Export.to_figure(vl, format: :svg, markup: :markdown, caption: "C:\\charts\\") ==
+               ~S{![](data:image/svg+xml;base64,PHN2Zz48L3N2Zz4= "C:\\charts\\")}
+
+      assert Export.to_figure(vl, format: :svg, markup: :markdown, caption: "One\ntwo") ==
+               ~S{![](data:image/svg+xml;base64,PHN2Zz48L3N2Zz4= "One two")}
+    end
+
+    test "makes the SVG image responsive when requested", %{bin: bin} do
+      write_svg_script!(bin, ~s{<svg width="400" height="300"></svg>})
+      vl = Vl.new() |> Vl.mark(:point)
+
+      svg = ~s{<svg preserveAspectRatio="xMidYMid meet" viewBox="0 0 400 300"></svg>}
+
+      assert Export.to_figure(vl, format: :svg, markup: :markdown, responsive: true) ==
+               "![](data:image/svg+xml;base64,#{Base.encode64(svg)})"
+    end
+
+    test "raises an error when invalid format is given" do
+      assert_raise ArgumentError, ~r/unsupported figure format/, fn ->
+        Export.to_figure(Vl.new(), format: :gif)
+      end
+    end
+  end
+
+  # Puts a fake npm executable in PATH, which reports the
+  # returned bin directory as the location of npm scripts
+  defp stub_npm(_context) do
+    dir = Path.join(System.tmp_dir!(), "vega-lite-test-#{System.unique_integer([:positive])}")
+    bin = Path.join(dir, "bin")
+    File.mkdir_p!(bin)
+    write_script!(Path.join(dir, "npm"), ~s{echo "#{bin}"})
+
+    path = System.get_env("PATH")
+    System.put_env("PATH", dir <> ":" <> path)
+
+    on_exit(fn ->
+      System.put_env("PATH", path)
+      File.rm_rf!(dir)
+    end)
+
+    %{bin: bin}
+  end
+
+  defp write_svg_script!(bin, svg) do
+    write_script!(Path.join(bin, "vl2svg"), "printf '#{svg}'")
+  end
+
+  defp write_script!(path, body) do
+    File.write!(path, "#!/bin/sh\n" <> body <> "\n")
+    File.chmod!(path, 0o755)
+  end
+end
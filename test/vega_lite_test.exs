@@ -3,6 +3,38 @@ defmodule VegaLiteTest do
 
   alias VegaLite, as: Vl
 
+  describe "from_editor_url/1" do
+    test "parses the specification embedded in the url" do
+      url =
+        "https://vega.github.io/editor/#/url/vega-lite/" <>
+          "N4IgJAzgxgFgpgWwIYgFwhgF0wBwqgegIDc4BzJAOjIEtMYBXAI0poHsDp5kTykBaADZ04JAKyUAVhDYA7EABoQyAE4BrNCBxsaszCAC+QA"
+
+      assert Vl.from_editor_url(url) == Vl.new() |> Vl.mark(:point)
+    end
+
+    test "ignores the trailing view path" do
+      vl = Vl.new(title: "Zażółć gęślą jaźń 📈") |> Vl.mark(:bar)
+      url = VegaLite.Export.to_editor_url(vl) <> "/view"
+
+      assert Vl.from_editor_url(url) == vl
+    end
+
+    test "raises an error when the url has no embedded specification" do
+      assert_raise ArgumentError, ~r/expected a Vega Editor URL/, fn ->
+        Vl.from_editor_url("https://vega.github.io/editor/#/examples")
+      end
+    end
+
+    test "raises an error when the embedded payload is not valid JSON" do
+      # The compressed "not json" string
+      url = "https://vega.github.io/editor/#/url/vega-lite/HYewLgBAVgzixA"
+
+      assert_raise ArgumentError, ~r/expected a Vega Editor URL/, fn ->
+        Vl.from_editor_url(url)
+      end
+    end
+  end
+
   describe "data/2" do
     test "raises an error when no properties are given" do
       assert_raise ArgumentError, "expected at least one data property, but none was given", fn ->